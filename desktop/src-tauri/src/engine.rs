@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::DesktopSettings;
+
+/// Mode d'inférence: `Cli` relance whisper.exe/tts à chaque appel, `Server`
+/// parle à un démon HTTP compatible OpenAI qui garde le modèle chargé.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Engine {
+    Cli,
+    Server,
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Engine::Cli
+    }
+}
+
+#[derive(Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+}
+
+/// POST le WAV en multipart sur `/v1/audio/transcriptions` et retourne le texte.
+pub fn transcribe_via_server(cfg: &DesktopSettings, wav_path: &str) -> Result<String, String> {
+    let url = format!(
+        "{}/v1/audio/transcriptions",
+        cfg.server_url.trim_end_matches('/')
+    );
+    let bytes = fs::read(wav_path).map_err(|e| e.to_string())?;
+    let file_name = Path::new(wav_path)
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("audio.wav")
+        .to_string();
+    let part = reqwest::blocking::multipart::Part::bytes(bytes)
+        .file_name(file_name)
+        .mime_str("audio/wav")
+        .map_err(|e| e.to_string())?;
+    let form = reqwest::blocking::multipart::Form::new()
+        .part("file", part)
+        .text("model", cfg.whisper_model_preset.clone())
+        .text("language", "fr");
+
+    let resp = reqwest::blocking::Client::new()
+        .post(&url)
+        .multipart(form)
+        .send()
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("inference server returned {}", resp.status()));
+    }
+    let parsed: TranscriptionResponse = resp.json().map_err(|e| e.to_string())?;
+    Ok(parsed.text)
+}
+
+#[derive(Serialize)]
+struct SpeechRequest<'a> {
+    model: &'a str,
+    input: &'a str,
+    voice: &'a str,
+}
+
+/// POST un texte JSON sur `/v1/audio/speech` et retourne les octets WAV.
+pub fn synthesize_via_server(cfg: &DesktopSettings, text: &str) -> Result<Vec<u8>, String> {
+    let url = format!("{}/v1/audio/speech", cfg.server_url.trim_end_matches('/'));
+    let body = SpeechRequest {
+        model: "tts-1",
+        input: text,
+        voice: &cfg.tts_voice,
+    };
+    let resp = reqwest::blocking::Client::new()
+        .post(&url)
+        .json(&body)
+        .send()
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("inference server returned {}", resp.status()));
+    }
+    resp.bytes()
+        .map(|b| b.to_vec())
+        .map_err(|e| e.to_string())
+}
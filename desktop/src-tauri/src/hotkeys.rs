@@ -0,0 +1,49 @@
+use auto_launch::AutoLaunch;
+use tauri::{AppHandle, GlobalShortcutManager, Manager};
+
+use crate::recording::{start_recording, stop_recording, SharedRecordingState};
+use crate::DesktopSettings;
+
+/// (Ré-)enregistre le raccourci global de bascule enregistrement décrit par
+/// `cfg.hotkeys`. Appelé au démarrage et à chaque `save_settings` pour que
+/// les changements s'appliquent sans redémarrer l'app.
+pub fn apply_hotkeys(app: &AppHandle, cfg: &DesktopSettings) -> Result<(), String> {
+    let mut mgr = app.global_shortcut_manager();
+    mgr.unregister_all().map_err(|e| e.to_string())?;
+
+    if !cfg.hotkeys.enabled || cfg.hotkeys.record_toggle.trim().is_empty() {
+        return Ok(());
+    }
+
+    let app_handle = app.clone();
+    mgr.register(&cfg.hotkeys.record_toggle, move || {
+        toggle_recording(&app_handle);
+    })
+    .map_err(|e| format!("register hotkey '{}': {}", cfg.hotkeys.record_toggle, e))
+}
+
+/// `stop_recording`'s `Ok(path)` isn't surfaced here: `recording::run_capture`
+/// emits a `recording-stopped` event with that same path (the only signal the
+/// frontend gets for a hands-free stop, since no command call is waiting on
+/// this result), so we only need to log the error case.
+fn toggle_recording(app: &AppHandle) {
+    let state = app.state::<SharedRecordingState>();
+    if state.is_active() {
+        if let Err(e) = stop_recording(state) {
+            eprintln!("stop_recording failed: {}", e);
+        }
+    } else if let Err(e) = start_recording(app.clone(), state) {
+        eprintln!("start_recording failed: {}", e);
+    }
+}
+
+/// Inscrit ou retire IVY du démarrage automatique de session.
+pub fn apply_autostart(start_on_login: bool) -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let auto = AutoLaunch::new("IVY", &exe.to_string_lossy(), &[] as &[&str]);
+    if start_on_login {
+        auto.enable().map_err(|e| e.to_string())
+    } else {
+        auto.disable().map_err(|e| e.to_string())
+    }
+}
@@ -1,11 +1,40 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod engine;
+mod hotkeys;
+mod recording;
+mod resolve;
+mod transcription;
+mod tts_protocol;
+mod update;
+
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fs::{self, File};
-use std::io::{Read, Write};
+use std::io::{BufRead, BufReader, Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use tauri::Manager;
+
+use engine::Engine;
+use recording::{start_recording, stop_recording, SharedRecordingState};
+use resolve::resolve_executables;
+use update::install_update;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeySettings {
+    pub enabled: bool,
+    pub record_toggle: String,
+}
+
+impl Default for HotkeySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            record_toggle: "CmdOrCtrl+Shift+Space".into(),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DesktopSettings {
@@ -19,6 +48,12 @@ pub struct DesktopSettings {
     pub tts_cmd: String,
     pub tts_voice: String,
     pub tts_rate: f32,
+    #[serde(default)]
+    pub hotkeys: HotkeySettings,
+    #[serde(default)]
+    pub start_on_login: bool,
+    #[serde(default)]
+    pub engine: Engine,
 }
 
 impl Default for DesktopSettings {
@@ -34,6 +69,9 @@ impl Default for DesktopSettings {
             tts_cmd: "tts".into(),
             tts_voice: "tts_models/fr/css10/vits".into(),
             tts_rate: 1.0,
+            hotkeys: HotkeySettings::default(),
+            start_on_login: false,
+            engine: Engine::default(),
         }
     }
 }
@@ -47,7 +85,7 @@ fn settings_path() -> PathBuf {
 }
 
 #[tauri::command]
-fn get_settings() -> Result<DesktopSettings, String> {
+pub(crate) fn get_settings() -> Result<DesktopSettings, String> {
     let p = settings_path();
     if p.exists() {
         let s = fs::read_to_string(&p).map_err(|e| e.to_string())?;
@@ -59,16 +97,22 @@ fn get_settings() -> Result<DesktopSettings, String> {
 }
 
 #[tauri::command]
-fn save_settings(cfg: DesktopSettings) -> Result<(), String> {
+fn save_settings(app: tauri::AppHandle, cfg: DesktopSettings) -> Result<(), String> {
+    let (cfg, _diagnostics) = resolve_executables(cfg)?;
     let p = settings_path();
     let s = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
     fs::write(p, s).map_err(|e| e.to_string())?;
+    hotkeys::apply_hotkeys(&app, &cfg)?;
+    hotkeys::apply_autostart(cfg.start_on_login)?;
     Ok(())
 }
 
 #[tauri::command]
-fn transcribe_wav(wav_path: String) -> Result<String, String> {
+fn transcribe_wav(app: tauri::AppHandle, wav_path: String) -> Result<String, String> {
     let cfg = get_settings()?;
+    if cfg.engine == Engine::Server {
+        return engine::transcribe_via_server(&cfg, &wav_path);
+    }
     let mut args: Vec<String> = vec![
         "--model".into(),
         cfg.whisper_model,
@@ -77,7 +121,6 @@ fn transcribe_wav(wav_path: String) -> Result<String, String> {
         "-l".into(),
         "fr".into(),
         "--output-txt".into(),
-        "--no-timestamps".into(),
     ];
     if cfg.vad {
         args.push("--vad".into());
@@ -91,10 +134,23 @@ fn transcribe_wav(wav_path: String) -> Result<String, String> {
     args.push("--output-dir".into());
     args.push(out_dir.to_string_lossy().to_string());
 
-    let status = Command::new(cfg.whisper_exe)
+    // stdout en pipe pour émettre les segments au fur et à mesure (pas d'attente
+    // silencieuse jusqu'à la fin de whisper sur les longs enregistrements)
+    let mut child = Command::new(cfg.whisper_exe)
         .args(&args)
-        .status()
+        .stdout(Stdio::piped())
+        .spawn()
         .map_err(|e| format!("whisper exec: {}", e))?;
+
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if let Some(segment) = transcription::parse_segment_line(&line) {
+                let _ = app.emit_all("transcription-segment", &segment);
+            }
+        }
+    }
+
+    let status = child.wait().map_err(|e| format!("whisper exec: {}", e))?;
     if !status.success() {
         return Err("whisper failed".into());
     }
@@ -110,31 +166,35 @@ fn transcribe_wav(wav_path: String) -> Result<String, String> {
 }
 
 #[tauri::command]
-fn tts_speak(text: String) -> Result<(), String> {
+fn tts_speak(app: tauri::AppHandle, text: String) -> Result<String, String> {
     let cfg = get_settings()?;
     // Génère un WAV temporaire via Coqui TTS
     let out = std::env::temp_dir().join("ivy_tts.wav");
-    let mut args: Vec<String> = vec![
-        "--text".into(),
-        text,
-        "--model_name".into(),
-        cfg.tts_voice,
-        "--out_path".into(),
-        out.to_string_lossy().to_string(),
-    ];
-    // Pas de flag standard pour le rate: dépend du modèle; laissé à 1.0
-    let status = Command::new(&cfg.tts_cmd)
-        .args(&args)
-        .status()
-        .map_err(|e| format!("tts exec: {}", e))?;
-    if !status.success() {
-        return Err("tts failed".into());
+    if cfg.engine == Engine::Server {
+        let bytes = engine::synthesize_via_server(&cfg, &text)?;
+        fs::write(&out, &bytes).map_err(|e| e.to_string())?;
+    } else {
+        let args: Vec<String> = vec![
+            "--text".into(),
+            text,
+            "--model_name".into(),
+            cfg.tts_voice,
+            "--out_path".into(),
+            out.to_string_lossy().to_string(),
+        ];
+        // Pas de flag standard pour le rate: dépend du modèle; laissé à 1.0
+        let status = Command::new(&cfg.tts_cmd)
+            .args(&args)
+            .status()
+            .map_err(|e| format!("tts exec: {}", e))?;
+        if !status.success() {
+            return Err("tts failed".into());
+        }
     }
-    // Lire via lecteur par défaut
-    let _ = Command::new("C:/Windows/System32/cmd.exe")
-        .args(["/C", &format!("start \"\" {}", out.to_string_lossy())])
-        .status();
-    Ok(())
+    // Le frontend joue le fichier via un <audio> pointant sur ce flux, pas
+    // de `tts_rate` côté modèle pour l'instant: géré via `playbackRate` côté UI.
+    tts_protocol::set_last(&app, out);
+    Ok("ivy-tts://last".into())
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -142,6 +202,9 @@ struct UpdateManifest {
     version: String,
     url: String,
     sha256: String,
+    /// Signature Ed25519 détachée (base64) des octets de l'installeur, vérifiée
+    /// après le SHA256 dans `download_and_verify`.
+    signature: String,
     notes: Option<String>,
 }
 
@@ -158,7 +221,11 @@ fn check_update() -> Result<Option<UpdateManifest>, String> {
 }
 
 #[tauri::command]
-fn download_and_verify(url: String, sha256_hex: String) -> Result<String, String> {
+fn download_and_verify(
+    url: String,
+    sha256_hex: String,
+    signature_b64: String,
+) -> Result<String, String> {
     let bytes = reqwest::blocking::get(&url)
         .map_err(|e| e.to_string())?
         .bytes()
@@ -170,20 +237,38 @@ fn download_and_verify(url: String, sha256_hex: String) -> Result<String, String
     if calc.to_lowercase() != sha256_hex.to_lowercase() {
         return Err("SHA256 mismatch".into());
     }
+    update::verify_signature(&bytes, &signature_b64)?;
     let path = std::env::temp_dir().join("ivy_desktop_update.msi");
     fs::write(&path, &bytes).map_err(|e| e.to_string())?;
     Ok(path.to_string_lossy().to_string())
 }
 
 fn main() {
-    tauri::Builder::default()
+    let builder = tts_protocol::register(tauri::Builder::default());
+    builder
+        .manage(SharedRecordingState::default())
+        .setup(|app| {
+            let handle = app.handle();
+            let cfg = get_settings().unwrap_or_default();
+            if let Err(e) = hotkeys::apply_hotkeys(&handle, &cfg) {
+                eprintln!("hotkey registration failed: {}", e);
+            }
+            if let Err(e) = hotkeys::apply_autostart(cfg.start_on_login) {
+                eprintln!("autostart registration failed: {}", e);
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             get_settings,
             save_settings,
             transcribe_wav,
             tts_speak,
             check_update,
-            download_and_verify
+            download_and_verify,
+            start_recording,
+            stop_recording,
+            resolve_executables,
+            install_update
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
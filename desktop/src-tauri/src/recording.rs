@@ -0,0 +1,247 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use hound::{SampleFormat, WavSpec, WavWriter};
+use tauri::{AppHandle, Manager, State};
+
+use crate::{get_settings, DesktopSettings};
+
+const SAMPLE_RATE: u32 = 16_000;
+const SILENCE_TIMEOUT_MS: u64 = 800;
+
+/// Etat partagé de l'enregistrement en cours, géré par Tauri (`manage`).
+#[derive(Default)]
+pub struct RecordingState {
+    inner: Mutex<Option<ActiveRecording>>,
+}
+
+struct ActiveRecording {
+    stop_flag: Arc<AtomicBool>,
+    wav_path: PathBuf,
+    join_handle: Option<thread::JoinHandle<Result<(), String>>>,
+}
+
+pub type SharedRecordingState = Arc<RecordingState>;
+
+impl RecordingState {
+    pub fn is_active(&self) -> bool {
+        self.inner.lock().map(|g| g.is_some()).unwrap_or(false)
+    }
+}
+
+#[tauri::command]
+pub fn start_recording(app: AppHandle, state: State<SharedRecordingState>) -> Result<(), String> {
+    let mut guard = state.inner.lock().map_err(|e| e.to_string())?;
+    if guard.is_some() {
+        return Err("recording already in progress".into());
+    }
+
+    let cfg = get_settings()?;
+    let wav_path = std::env::temp_dir().join("ivy_recording.wav");
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let shared_state: SharedRecordingState = (*state).clone();
+
+    let join_handle = spawn_capture_thread(
+        app,
+        cfg,
+        wav_path.clone(),
+        stop_flag.clone(),
+        shared_state,
+    )?;
+
+    *guard = Some(ActiveRecording {
+        stop_flag,
+        wav_path,
+        join_handle: Some(join_handle),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_recording(state: State<SharedRecordingState>) -> Result<String, String> {
+    let active = state
+        .inner
+        .lock()
+        .map_err(|e| e.to_string())?
+        .take()
+        .ok_or("no recording in progress")?;
+
+    active.stop_flag.store(true, Ordering::SeqCst);
+    if let Some(handle) = active.join_handle {
+        handle
+            .join()
+            .map_err(|_| "capture thread panicked".to_string())??;
+    }
+    Ok(active.wav_path.to_string_lossy().to_string())
+}
+
+/// Lance la capture cpal sur un thread dédié: cpal::Stream n'est pas Send sur
+/// toutes les plateformes, donc le stream vit et meurt entièrement sur ce thread.
+fn spawn_capture_thread(
+    app: AppHandle,
+    cfg: DesktopSettings,
+    wav_path: PathBuf,
+    stop_flag: Arc<AtomicBool>,
+    shared_state: SharedRecordingState,
+) -> Result<thread::JoinHandle<Result<(), String>>, String> {
+    let host = cpal::default_host();
+    let device = host
+        .default_input_device()
+        .ok_or("no input device available")?;
+    let supported = device
+        .default_input_config()
+        .map_err(|e| format!("input config: {}", e))?;
+
+    Ok(thread::spawn(move || {
+        run_capture(app, cfg, device, supported, wav_path, stop_flag, shared_state)
+    }))
+}
+
+/// Capture jusqu'à l'arrêt (manuel ou auto-stop), puis, dans tous les cas,
+/// libère le slot partagé s'il est toujours le nôtre et notifie le frontend.
+/// C'est la seule façon de savoir qu'un enregistrement hands-free (hotkey,
+/// VAD, `max_seconds`) vient de se terminer: personne n'appelle
+/// `stop_recording` dans ce cas, donc son chemin de retour ne sera jamais lu.
+fn run_capture(
+    app: AppHandle,
+    cfg: DesktopSettings,
+    device: cpal::Device,
+    supported: cpal::SupportedStreamConfig,
+    wav_path: PathBuf,
+    stop_flag: Arc<AtomicBool>,
+    shared_state: SharedRecordingState,
+) -> Result<(), String> {
+    let result = run_capture_inner(&app, cfg, device, supported, &wav_path, &stop_flag);
+
+    if let Ok(mut guard) = shared_state.inner.lock() {
+        if matches!(guard.as_ref(), Some(active) if Arc::ptr_eq(&active.stop_flag, &stop_flag)) {
+            *guard = None;
+        }
+    }
+
+    match &result {
+        Ok(()) => {
+            let _ = app.emit_all("recording-stopped", wav_path.to_string_lossy().to_string());
+        }
+        Err(e) => {
+            eprintln!("recording capture failed: {}", e);
+            let _ = app.emit_all("recording-error", e.clone());
+        }
+    }
+
+    result
+}
+
+fn run_capture_inner(
+    app: &AppHandle,
+    cfg: DesktopSettings,
+    device: cpal::Device,
+    supported: cpal::SupportedStreamConfig,
+    wav_path: &Path,
+    stop_flag: &Arc<AtomicBool>,
+) -> Result<(), String> {
+    let channels = supported.channels() as usize;
+    let input_sample_rate = supported.sample_rate().0;
+
+    // décimation naïve (pas de ré-échantillonnage de qualité, juste un pas entier): le
+    // header WAV doit refléter le taux réel post-décimation, pas SAMPLE_RATE à l'aveugle,
+    // sinon un device à 44100/22050 Hz écrit un fichier dont le header ment sur le tempo.
+    let decim = (input_sample_rate / SAMPLE_RATE).max(1);
+    let effective_sample_rate = input_sample_rate / decim;
+
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: effective_sample_rate,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let _ = fs::create_dir_all(wav_path.parent().unwrap_or_else(|| Path::new(".")));
+    let writer = Arc::new(Mutex::new(Some(
+        WavWriter::create(wav_path, spec).map_err(|e| e.to_string())?,
+    )));
+
+    let speech_started = Arc::new(AtomicBool::new(false));
+    let last_speech_ms = Arc::new(AtomicU32::new(0));
+    let start = Instant::now();
+
+    let writer_cb = writer.clone();
+    let speech_started_cb = speech_started.clone();
+    let last_speech_ms_cb = last_speech_ms.clone();
+    let vad_enabled = cfg.vad;
+    let vad_threshold = cfg.vad_threshold;
+    let app_cb = app.clone();
+
+    let err_fn = |err| eprintln!("cpal stream error: {}", err);
+    let config = supported.config();
+
+    let stream = device
+        .build_input_stream(
+            &config,
+            move |data: &[f32], _| {
+                let mut frame_sum_sq = 0.0f32;
+                let mut frame_len = 0usize;
+                let mut guard = writer_cb.lock().unwrap();
+                let w = match guard.as_mut() {
+                    Some(w) => w,
+                    None => return,
+                };
+                for (i, frame) in data.chunks(channels).enumerate() {
+                    if i % decim as usize != 0 {
+                        continue;
+                    }
+                    let mono = frame.iter().sum::<f32>() / channels as f32;
+                    frame_sum_sq += mono * mono;
+                    frame_len += 1;
+                    let sample = (mono.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+                    let _ = w.write_sample(sample);
+                }
+                drop(guard);
+                if frame_len == 0 {
+                    return;
+                }
+                let rms = (frame_sum_sq / frame_len as f32).sqrt();
+                let _ = app_cb.emit_all("audio-level", rms);
+
+                if vad_enabled {
+                    let elapsed_ms = start.elapsed().as_millis() as u32;
+                    if rms > vad_threshold {
+                        speech_started_cb.store(true, Ordering::Relaxed);
+                        last_speech_ms_cb.store(elapsed_ms, Ordering::Relaxed);
+                    }
+                }
+            },
+            err_fn,
+            None,
+        )
+        .map_err(|e| format!("build input stream: {}", e))?;
+
+    stream.play().map_err(|e| format!("stream play: {}", e))?;
+
+    loop {
+        thread::sleep(Duration::from_millis(50));
+        if stop_flag.load(Ordering::SeqCst) {
+            break;
+        }
+        let elapsed_ms = start.elapsed().as_millis() as u32;
+        if elapsed_ms >= cfg.max_seconds.saturating_mul(1000) {
+            break;
+        }
+        if cfg.vad && speech_started.load(Ordering::Relaxed) {
+            let silence_for = elapsed_ms.saturating_sub(last_speech_ms.load(Ordering::Relaxed));
+            if silence_for as u64 >= SILENCE_TIMEOUT_MS {
+                break;
+            }
+        }
+    }
+
+    drop(stream);
+    if let Some(w) = writer.lock().unwrap().take() {
+        w.finalize().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::DesktopSettings;
+
+/// Diagnostic d'un exécutable externe attendu par IVY (whisper, tts, ...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolDiagnostic {
+    pub name: String,
+    pub found: bool,
+    pub path: Option<String>,
+    pub is_executable: bool,
+}
+
+const WHISPER_CANDIDATES: &[&str] = &["whisper", "whisper-cli", "main"];
+const TTS_CANDIDATES: &[&str] = &["tts"];
+
+/// Si les chemins configurés n'existent pas, cherche dans le PATH des noms
+/// usuels (`whisper`, `whisper-cli`, `main`, `tts`) avec la crate `which`, et
+/// retourne les settings mis à jour avec un diagnostic par outil.
+#[tauri::command]
+pub fn resolve_executables(
+    mut cfg: DesktopSettings,
+) -> Result<(DesktopSettings, Vec<ToolDiagnostic>), String> {
+    if !Path::new(&cfg.whisper_exe).exists() {
+        if let Some(found) = find_first(WHISPER_CANDIDATES) {
+            cfg.whisper_exe = found;
+        }
+    }
+    if !Path::new(&cfg.tts_cmd).exists() {
+        if let Some(found) = find_first(TTS_CANDIDATES) {
+            cfg.tts_cmd = found;
+        }
+    }
+
+    let diagnostics = vec![
+        diagnose("whisper", &cfg.whisper_exe),
+        diagnose("tts", &cfg.tts_cmd),
+    ];
+    Ok((cfg, diagnostics))
+}
+
+fn find_first(candidates: &[&str]) -> Option<String> {
+    candidates
+        .iter()
+        .find_map(|name| which::which(name).ok())
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+fn diagnose(name: &str, configured: &str) -> ToolDiagnostic {
+    let p = Path::new(configured);
+    let found = p.exists() || which::which(configured).is_ok();
+    ToolDiagnostic {
+        name: name.into(),
+        found,
+        path: if found {
+            Some(configured.to_string())
+        } else {
+            None
+        },
+        is_executable: found && is_executable(p),
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(p: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(p)
+        .map(|m| m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(p: &Path) -> bool {
+    p.exists()
+}
@@ -0,0 +1,34 @@
+use serde::Serialize;
+
+/// Un segment whisper.cpp parsé depuis une ligne `[hh:mm:ss.mmm --> ...] texte`.
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+/// Parse une ligne de stdout whisper.cpp, ou `None` si elle ne décrit pas un segment.
+pub fn parse_segment_line(line: &str) -> Option<TranscriptionSegment> {
+    let line = line.trim();
+    let rest = line.strip_prefix('[')?;
+    let (range, text) = rest.split_once(']')?;
+    let (start_str, end_str) = range.split_once("-->")?;
+    let start_ms = parse_timestamp(start_str.trim())?;
+    let end_ms = parse_timestamp(end_str.trim())?;
+    Some(TranscriptionSegment {
+        start_ms,
+        end_ms,
+        text: text.trim().to_string(),
+    })
+}
+
+fn parse_timestamp(s: &str) -> Option<u64> {
+    let (hms, millis) = s.split_once('.')?;
+    let mut parts = hms.split(':');
+    let h: u64 = parts.next()?.parse().ok()?;
+    let m: u64 = parts.next()?.parse().ok()?;
+    let sec: u64 = parts.next()?.parse().ok()?;
+    let ms: u64 = millis.parse().ok()?;
+    Some(((h * 3600 + m * 60 + sec) * 1000) + ms)
+}
@@ -0,0 +1,153 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use tauri::http::{Request, Response, ResponseBuilder};
+use tauri::{AppHandle, Manager, Wry};
+
+/// Chemin du dernier WAV généré par `tts_speak`, servi par `ivy-tts://last`.
+#[derive(Default)]
+pub struct LastTtsPath(Mutex<Option<PathBuf>>);
+
+pub fn set_last(app: &AppHandle, path: PathBuf) {
+    let state = app.state::<LastTtsPath>();
+    *state.0.lock().unwrap() = Some(path);
+}
+
+/// Enregistre le protocole `ivy-tts://` sur le builder: chargement asynchrone
+/// du fichier (hors thread UI) et support des requêtes `Range` pour que
+/// l'élément `<audio>` du frontend puisse seek sans tout recharger.
+pub fn register(builder: tauri::Builder<Wry>) -> tauri::Builder<Wry> {
+    builder
+        .manage(LastTtsPath::default())
+        .register_asynchronous_uri_scheme_protocol("ivy-tts", |app, request, responder| {
+            let app = app.clone();
+            std::thread::spawn(move || {
+                responder.respond(build_response(&app, &request));
+            });
+        })
+}
+
+fn build_response(app: &AppHandle, request: &Request) -> Response {
+    serve(app, request).unwrap_or_else(|e| {
+        ResponseBuilder::new()
+            .status(404)
+            .body(e.into_bytes())
+            .expect("building error response")
+    })
+}
+
+fn serve(app: &AppHandle, request: &Request) -> Result<Response, String> {
+    let state = app.state::<LastTtsPath>();
+    let path = state
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .clone()
+        .ok_or_else(|| "no tts audio available yet".to_string())?;
+    let data = fs::read(&path).map_err(|e| e.to_string())?;
+    let total = data.len() as u64;
+
+    let range_header = request.headers().get("range").and_then(|v| v.to_str().ok());
+    match resolve_range(range_header, total) {
+        RangeResult::Partial(start, end) => {
+            let chunk = data[start as usize..=end as usize].to_vec();
+            ResponseBuilder::new()
+                .status(206)
+                .header("Content-Type", "audio/wav")
+                .header("Accept-Ranges", "bytes")
+                .header("Content-Range", format!("bytes {}-{}/{}", start, end, total))
+                .header("Content-Length", chunk.len().to_string())
+                .body(chunk)
+                .map_err(|e| e.to_string())
+        }
+        RangeResult::NotSatisfiable => ResponseBuilder::new()
+            .status(416)
+            .header("Content-Range", format!("bytes */{}", total))
+            .body(Vec::new())
+            .map_err(|e| e.to_string()),
+        RangeResult::Full => ResponseBuilder::new()
+            .status(200)
+            .header("Content-Type", "audio/wav")
+            .header("Accept-Ranges", "bytes")
+            .header("Content-Length", total.to_string())
+            .body(data)
+            .map_err(|e| e.to_string()),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RangeResult {
+    /// Pas de `Range`, ou en-tête qu'on ne sait pas parser: servir le fichier entier.
+    Full,
+    Partial(u64, u64),
+    /// `Range` syntaxiquement valide mais hors bornes (fichier vide, requête
+    /// périmée après qu'`ivy-tts://last` a changé, bornes inversées, ...).
+    NotSatisfiable,
+}
+
+fn resolve_range(range: Option<&str>, total: u64) -> RangeResult {
+    let Some(range) = range else {
+        return RangeResult::Full;
+    };
+    let Some(spec) = range.strip_prefix("bytes=") else {
+        return RangeResult::Full;
+    };
+    let mut parts = spec.splitn(2, '-');
+    let Some(start) = parts.next().and_then(|s| s.parse::<u64>().ok()) else {
+        return RangeResult::Full;
+    };
+    let end = parts
+        .next()
+        .filter(|s| !s.is_empty())
+        .and_then(|s| s.parse::<u64>().ok());
+
+    if total == 0 || start >= total {
+        return RangeResult::NotSatisfiable;
+    }
+    let end = end.unwrap_or(total - 1).min(total - 1);
+    if start > end {
+        return RangeResult::NotSatisfiable;
+    }
+    RangeResult::Partial(start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_range_header_serves_full_file() {
+        assert_eq!(resolve_range(None, 100), RangeResult::Full);
+    }
+
+    #[test]
+    fn range_on_empty_file_is_not_satisfiable() {
+        assert_eq!(resolve_range(Some("bytes=0-10"), 0), RangeResult::NotSatisfiable);
+    }
+
+    #[test]
+    fn start_past_end_of_file_is_not_satisfiable() {
+        assert_eq!(resolve_range(Some("bytes=1000-2000"), 100), RangeResult::NotSatisfiable);
+    }
+
+    #[test]
+    fn inverted_bounds_are_not_satisfiable() {
+        assert_eq!(resolve_range(Some("bytes=50-10"), 100), RangeResult::NotSatisfiable);
+    }
+
+    #[test]
+    fn open_ended_range_clamps_to_last_byte() {
+        assert_eq!(resolve_range(Some("bytes=10-"), 100), RangeResult::Partial(10, 99));
+    }
+
+    #[test]
+    fn end_beyond_total_is_clamped_not_rejected() {
+        assert_eq!(resolve_range(Some("bytes=0-999"), 100), RangeResult::Partial(0, 99));
+    }
+
+    #[test]
+    fn unparseable_range_header_falls_back_to_full_file() {
+        assert_eq!(resolve_range(Some("not-a-range"), 100), RangeResult::Full);
+    }
+}
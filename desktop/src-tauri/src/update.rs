@@ -0,0 +1,43 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use std::process::Command;
+
+/// Clé publique Ed25519 de confiance, utilisée pour valider la signature des
+/// installeurs distribués via le manifeste de mise à jour. Le SHA256 du
+/// manifeste seul ne protège pas contre un serveur compromis qui servirait un
+/// binaire malveillant avec un hash assorti.
+///
+/// Moitié publique du keypair de signature de la release pipeline; la clé
+/// privée correspondante ne doit jamais se trouver dans ce dépôt.
+const TRUSTED_PUBLIC_KEY: [u8; 32] = [
+    0xc6, 0xca, 0x53, 0x21, 0x7f, 0x26, 0x27, 0x8d, 0x77, 0x78, 0x4a, 0x63, 0x67, 0x0f, 0x9d, 0x7d,
+    0x1f, 0x44, 0x5b, 0x08, 0xf5, 0xdb, 0xe2, 0xe2, 0x14, 0x1f, 0x14, 0x3f, 0x5f, 0x6d, 0x06, 0xef,
+];
+
+/// Vérifie que `signature_b64` est une signature Ed25519 valide de `bytes`
+/// sous la clé publique embarquée. Doit être appelé après la vérification
+/// SHA256, jamais à sa place.
+pub fn verify_signature(bytes: &[u8], signature_b64: &str) -> Result<(), String> {
+    let sig_bytes = BASE64
+        .decode(signature_b64)
+        .map_err(|e| format!("invalid signature encoding: {}", e))?;
+    let sig_array: [u8; 64] = sig_bytes
+        .try_into()
+        .map_err(|_| "signature must be 64 bytes".to_string())?;
+    let signature = Signature::from_bytes(&sig_array);
+    let key = VerifyingKey::from_bytes(&TRUSTED_PUBLIC_KEY).map_err(|e| e.to_string())?;
+    key.verify(bytes, &signature)
+        .map_err(|_| "signature verification failed".to_string())
+}
+
+/// Lance le MSI vérifié via `msiexec /i` puis quitte l'app pour que
+/// l'installeur puisse remplacer les fichiers en cours d'utilisation.
+#[tauri::command]
+pub fn install_update(path: String) -> Result<(), String> {
+    Command::new("msiexec")
+        .args(["/i", &path])
+        .spawn()
+        .map_err(|e| format!("msiexec exec: {}", e))?;
+    std::process::exit(0);
+}